@@ -4,40 +4,62 @@ use zenoh::*;
 use futures::prelude::*;
 use futures::select;
 use async_std::task;
+use opentelemetry::metrics::MetricsError;
 use opentelemetry::trace::TraceError;
 use opentelemetry::{
     global,
-    sdk::{trace as sdktrace, propagation::TraceContextPropagator},
+    sdk::{
+        propagation::{BaggagePropagator, TextMapCompositePropagator, TraceContextPropagator},
+        trace as sdktrace,
+        Resource,
+    },
     trace::{FutureExt, TraceContextExt, Tracer},
     Context,
     KeyValue,
 };
 use opentelemetry_semantic_conventions::{resource, trace};
 use opentelemetry_jaeger;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_datadog::DatadogPropagator;
 use std::collections::HashMap;
 use std::time;
 use serde::{Deserialize, Serialize};
 use rand::Rng;
 
+mod telemetry_worker;
+use telemetry_worker::{CollectorConfig, TelemetryRecord, TelemetryWorker};
+
 const VERSION: &str = env!("CARGO_PKG_VERSION");
+// Verbosity tiers for the lifecycle events sensor/computing/motion hand to
+// the telemetry worker; a collector only receives a record when its
+// configured --telemetry-collector verbosity is at least this high. SUMMARY
+// is a coarse "a message moved through this stage" event meant for every
+// collector (e.g. a remote low-verbosity aggregator); DETAIL additionally
+// carries the per-hop sleep_time and is meant for a high-verbosity local
+// collector such as a dev-box Jaeger.
+const TELEMETRY_VERBOSITY_SUMMARY: u8 = 0;
+const TELEMETRY_VERBOSITY_DETAIL: u8 = 5;
 
 #[async_std::main]
 async fn main() {
-    let (config, collector, action) = parse_args();
-    println!("collector path: {} , action: {}", collector, action);
+    let (config, collector, action, exporter, telemetry_collectors, context_carrier, metrics_collector) =
+        parse_args();
+    println!("collector path: {} , action: {} , exporter: {}", collector, action, exporter);
 
     // initate tracer
-    let _ = init_tracer(&action, VERSION,  &collector).unwrap();
+    let _ = init_tracer(&action, VERSION, &collector, &exporter).unwrap();
+    init_meter(&metrics_collector).unwrap();
+    let telemetry = TelemetryWorker::spawn(telemetry_collectors);
 
     let zenoh = Zenoh::new(config.into()).await.unwrap();
     let workspace = zenoh.workspace(None).await.unwrap();
 
     if action.as_str() == "sensor" {
-        sensor(workspace).await;
+        sensor(workspace, &telemetry, &context_carrier).await;
     } else if action.as_str() == "computing" {
-       computing(workspace).await;
+       computing(workspace, &telemetry, &context_carrier).await;
     } else if action.as_str() == "motion"{
-        motion(workspace).await;
+        motion(workspace, &telemetry, &context_carrier).await;
     }
 
     zenoh.close().await.unwrap();
@@ -45,13 +67,127 @@ async fn main() {
     opentelemetry::global::shutdown_tracer_provider();
 }
 
+// The clean business-data schema: no tracing fields, so it reads the same
+// whether the propagated context travelled alongside it in the payload or
+// out-of-band in zenoh sideband metadata.
 #[derive(Serialize, Deserialize, Debug)]
 struct Message{
+    // Random per-message id. Used to correlate a `--context-carrier=sideband`
+    // message with its companion context key, since a path is published to
+    // repeatedly and a single shared companion key would race.
+    id: u64,
     sleep_time: u64,
-    span_context: String,
+    // Epoch time (ms) at which this message was published, used to compute
+    // end-to-end per-hop latency on the receiving side.
+    publish_time: u64,
+}
+
+// Wire format for `--context-carrier=payload`: the propagated context map
+// (traceparent, tracestate, baggage, ...) flattened alongside the message.
+#[derive(Serialize, Deserialize, Debug)]
+struct MessageWithContext {
+    #[serde(flatten)]
+    message: Message,
+    context: HashMap<String, String>,
+}
+
+// Epoch time in milliseconds, used to timestamp messages for latency metrics.
+fn now_millis() -> u64 {
+    time::SystemTime::now()
+        .duration_since(time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+// Key-expression prefix the sideband-carried context for a message published
+// to `path` lives under; the message's own id is appended to it.
+//
+// Note: this is deliberately NOT read back with `workspace.get()`. A bare
+// `put()` is only retroactively queryable if a Storage is declared for the
+// key expression, and this example doesn't stand one up, so `get()` would
+// come back empty on a real deployment and silently drop every propagated
+// context. Instead, the context is published to a key expression nested
+// under `path`, and the receiver subscribes to `path`'s whole subtree
+// (`path/**`) so both the message and its companion context arrive as
+// ordinary subscription events; see `sideband_context_id`.
+fn sideband_context_prefix(path: &str) -> String {
+    format!("{}/_ctx/", path)
+}
+
+fn sideband_path(path: &str, id: u64) -> String {
+    format!("{}{}", sideband_context_prefix(path), id)
+}
+
+// If `received_path` is a sideband companion key published under `base_path`,
+// returns the message id it carries context for.
+fn sideband_context_id(received_path: &str, base_path: &str) -> Option<u64> {
+    received_path.strip_prefix(&sideband_context_prefix(base_path))?.parse().ok()
+}
+
+// Publishes `message` to `path`, carrying `context` either flattened into the
+// payload or under a companion sideband key, depending on `carrier`.
+async fn publish_message(
+    workspace: &zenoh::Workspace<'_>,
+    path: &str,
+    message: Message,
+    context: HashMap<String, String>,
+    carrier: &str,
+    cx: Context,
+) {
+    if carrier == "sideband" {
+        let serialized_context = serde_json::to_string(&context).unwrap();
+        workspace
+            .put(&sideband_path(path, message.id).try_into().unwrap(), serialized_context.into())
+            .await
+            .unwrap();
+
+        let serialized_message = serde_json::to_string(&message).unwrap();
+        workspace
+            .put(&path.try_into().unwrap(), serialized_message.into())
+            .with_context(cx)
+            .await
+            .unwrap();
+    } else {
+        let serialized = serde_json::to_string(&MessageWithContext { message, context }).unwrap();
+        workspace
+            .put(&path.try_into().unwrap(), serialized.into())
+            .with_context(cx)
+            .await
+            .unwrap();
+    }
 }
 
-async fn sensor(workspace: zenoh::Workspace<'_>) {
+// Reverse of `publish_message`: extracts the propagated context for `value`
+// (received on the subscribed path) and returns the clean `Message`
+// alongside it. For `carrier == "sideband"`, the context must already have
+// been routed into `pending_context` by the caller (see `sideband_context_id`)
+// since it travels as a separate subscription event.
+fn receive_message(
+    value: &str,
+    carrier: &str,
+    pending_context: &mut HashMap<u64, HashMap<String, String>>,
+) -> (Message, HashMap<String, String>) {
+    if carrier == "sideband" {
+        let message: Message = serde_json::from_str(value).unwrap();
+        let context = pending_context.remove(&message.id).unwrap_or_default();
+        (message, context)
+    } else {
+        let envelope: MessageWithContext = serde_json::from_str(value).unwrap();
+        (envelope.message, envelope.context)
+    }
+}
+
+async fn sensor(workspace: zenoh::Workspace<'_>, telemetry: &TelemetryWorker, carrier: &str) {
+    let meter = global::meter("zenoh-pipeline");
+    let sleep_histogram = meter
+        .u64_histogram("sensor.sleep_time")
+        .with_description("Simulated time spent acquiring sensor data, in milliseconds")
+        .init();
+    let publish_counter = meter
+        .u64_counter("sensor_data.published")
+        .with_description("Number of messages published to /sensor_data")
+        .init();
+
     let tracer = global::tracer("Sensor.rs");
     // Use tracer.start("span_name") could start the span without span builder
     let span = tracer
@@ -72,29 +208,58 @@ async fn sensor(workspace: zenoh::Workspace<'_>) {
     task::sleep(time::Duration::from_millis(rng.gen_range(0..100))).await;
     
     let message = Message {
+        id: rng.gen(),
         sleep_time: rng.gen_range(50..150),
-        span_context: injector["traceparent"].clone(),
+        publish_time: now_millis(),
     };
-    let serialized_message = serde_json::to_string(&message).unwrap();
 
-    cx.span().add_event("sensor data".into(), 
+    cx.span().add_event("sensor data".into(),
         vec![
             KeyValue::new("sleeping time", message.sleep_time.to_string()),
-            KeyValue::new("span context", message.span_context.to_string())
+            KeyValue::new("span context", format!("{:?}", injector))
             ]
-    );  
-    workspace
-        .put(&"/sensor_data".try_into().unwrap(), serialized_message.into())
-        .with_context(cx.clone())
-        .await
-        .unwrap();
+    );
+    sleep_histogram.record(message.sleep_time, &[]);
+    telemetry.send(TelemetryRecord {
+        verbosity: TELEMETRY_VERBOSITY_SUMMARY,
+        payload: "sensor: published /sensor_data".to_string(),
+    });
+    telemetry.send(TelemetryRecord {
+        verbosity: TELEMETRY_VERBOSITY_DETAIL,
+        payload: format!("sensor: published /sensor_data sleep_time={}", message.sleep_time),
+    });
+    publish_message(&workspace, "/sensor_data", message, injector, carrier, cx).await;
+    publish_counter.add(1, &[KeyValue::new("key", "/sensor_data")]);
 }
 
-async fn computing(workspace: zenoh::Workspace<'_>) {
+async fn computing(workspace: zenoh::Workspace<'_>, telemetry: &TelemetryWorker, carrier: &str) {
+    let meter = global::meter("zenoh-pipeline");
+    let sleep_histogram = meter
+        .u64_histogram("computing.sleep_time")
+        .with_description("Simulated time spent computing the action, in milliseconds")
+        .init();
+    let latency_histogram = meter
+        .u64_histogram("sensor_data.publish_to_receive_latency")
+        .with_description("Wall-clock latency from sensor publish to computing receive, in milliseconds")
+        .init();
+    let received_counter = meter
+        .u64_counter("sensor_data.received")
+        .with_description("Number of messages received from /sensor_data")
+        .init();
+    let published_counter = meter
+        .u64_counter("action.published")
+        .with_description("Number of messages published to /action")
+        .init();
+
+    // Subscribe to the whole /sensor_data subtree, not just the exact path:
+    // for --context-carrier=sideband the propagated context arrives as its
+    // own sample under /sensor_data/_ctx/<id>, and this is the only way to
+    // receive it without a zenoh Storage (see `sideband_context_prefix`).
     let mut change_stream = workspace
-    .subscribe(&"/sensor_data".try_into().unwrap())
+    .subscribe(&"/sensor_data/**".try_into().unwrap())
     .await
     .unwrap();
+    let mut pending_context: HashMap<u64, HashMap<String, String>> = HashMap::new();
 
     let mut stdin = async_std::io::stdin();
     let mut input = [0u8];
@@ -102,10 +267,19 @@ async fn computing(workspace: zenoh::Workspace<'_>) {
         select!(
             change = change_stream.next().fuse() => {
                 let change = change.unwrap();
-                let mut req_header = HashMap::new();
+                let received_path = change.path.to_string();
+                if carrier == "sideband" {
+                    if let Some(id) = sideband_context_id(&received_path, "/sensor_data") {
+                        if let Some(Value::StringUtf8(value)) = change.value {
+                            if let Ok(context) = serde_json::from_str(&value) {
+                                pending_context.insert(id, context);
+                            }
+                        }
+                        continue;
+                    }
+                }
                 if let Value::StringUtf8(value) = change.value.unwrap(){
-                    let message: Message = serde_json::from_str(&value).unwrap();
-                    req_header.insert("traceparent".to_string(), message.span_context.clone());
+                    let (message, mut req_header) = receive_message(&value, carrier, &mut pending_context);
 
                     println!(
                         ">> [Subscription listener] received {:?} for {} : {:?} with timestamp {}",
@@ -115,6 +289,13 @@ async fn computing(workspace: zenoh::Workspace<'_>) {
                         change.timestamp
                     );
 
+                    received_counter.add(1, &[KeyValue::new("key", "/sensor_data")]);
+                    latency_histogram.record(now_millis().saturating_sub(message.publish_time), &[]);
+                    telemetry.send(TelemetryRecord {
+                        verbosity: TELEMETRY_VERBOSITY_DETAIL,
+                        payload: format!("computing: received /sensor_data sleep_time={}", message.sleep_time),
+                    });
+
                     // Extract trace format to get parent context
                     let parent_cx = global::get_text_map_propagator(|propagator| propagator.extract(&req_header));
                     let tracer = global::tracer("Computing.rs");
@@ -133,19 +314,26 @@ async fn computing(workspace: zenoh::Workspace<'_>) {
                     
                     // Sleep to simulate computing the action
                     task::sleep(time::Duration::from_millis(message.sleep_time)).await;
+                    sleep_histogram.record(message.sleep_time, &[]);
 
                     let mut rng = rand::thread_rng();
-                    let message = Message {
+                    let new_message = Message {
+                        id: rng.gen(),
                         sleep_time: rng.gen_range(0..100),
-                        span_context: req_header["traceparent"].clone(),
+                        publish_time: now_millis(),
                     };
-                    let serialized_message = serde_json::to_string(&message).unwrap();
+                    let sleep_time = new_message.sleep_time;
 
-                    workspace
-                        .put(&"/action".try_into().unwrap(), serialized_message.into())
-                        .with_context(cx.clone())
-                        .await
-                        .unwrap();
+                    publish_message(&workspace, "/action", new_message, req_header, carrier, cx).await;
+                    published_counter.add(1, &[KeyValue::new("key", "/action")]);
+                    telemetry.send(TelemetryRecord {
+                        verbosity: TELEMETRY_VERBOSITY_SUMMARY,
+                        payload: "computing: processed /sensor_data -> /action".to_string(),
+                    });
+                    telemetry.send(TelemetryRecord {
+                        verbosity: TELEMETRY_VERBOSITY_DETAIL,
+                        payload: format!("computing: published /action sleep_time={}", sleep_time),
+                    });
                 };
             }
 
@@ -157,11 +345,28 @@ async fn computing(workspace: zenoh::Workspace<'_>) {
     change_stream.close().await.unwrap();
 }
 
-async fn motion(workspace: zenoh::Workspace<'_>) {
+async fn motion(workspace: zenoh::Workspace<'_>, telemetry: &TelemetryWorker, carrier: &str) {
+    let meter = global::meter("zenoh-pipeline");
+    let sleep_histogram = meter
+        .u64_histogram("motion.sleep_time")
+        .with_description("Simulated time spent running motion control, in milliseconds")
+        .init();
+    let latency_histogram = meter
+        .u64_histogram("action.publish_to_receive_latency")
+        .with_description("Wall-clock latency from computing publish to motion receive, in milliseconds")
+        .init();
+    let received_counter = meter
+        .u64_counter("action.received")
+        .with_description("Number of messages received from /action")
+        .init();
+
+    // Subscribe to the whole /action subtree, not just the exact path: see
+    // the matching comment in `computing`.
     let mut change_stream = workspace
-    .subscribe(&"/action".try_into().unwrap())
+    .subscribe(&"/action/**".try_into().unwrap())
     .await
     .unwrap();
+    let mut pending_context: HashMap<u64, HashMap<String, String>> = HashMap::new();
 
     let mut stdin = async_std::io::stdin();
     let mut input = [0u8];
@@ -169,10 +374,19 @@ async fn motion(workspace: zenoh::Workspace<'_>) {
         select!(
             change = change_stream.next().fuse() => {
                 let change = change.unwrap();
-                let mut req_header = HashMap::new();
+                let received_path = change.path.to_string();
+                if carrier == "sideband" {
+                    if let Some(id) = sideband_context_id(&received_path, "/action") {
+                        if let Some(Value::StringUtf8(value)) = change.value {
+                            if let Ok(context) = serde_json::from_str(&value) {
+                                pending_context.insert(id, context);
+                            }
+                        }
+                        continue;
+                    }
+                }
                 if let Value::StringUtf8(value) = change.value.unwrap(){
-                    let message: Message = serde_json::from_str(&value).unwrap();
-                    req_header.insert("traceparent".to_string(), message.span_context.clone());
+                    let (message, req_header) = receive_message(&value, carrier, &mut pending_context);
 
                     println!(
                         ">> [Subscription listener] received {:?} for {} : {:?} with timestamp {}",
@@ -181,7 +395,18 @@ async fn motion(workspace: zenoh::Workspace<'_>) {
                         message,
                         change.timestamp
                     );
-                    
+
+                    received_counter.add(1, &[KeyValue::new("key", "/action")]);
+                    latency_histogram.record(now_millis().saturating_sub(message.publish_time), &[]);
+                    telemetry.send(TelemetryRecord {
+                        verbosity: TELEMETRY_VERBOSITY_SUMMARY,
+                        payload: "motion: processed /action".to_string(),
+                    });
+                    telemetry.send(TelemetryRecord {
+                        verbosity: TELEMETRY_VERBOSITY_DETAIL,
+                        payload: format!("motion: received /action sleep_time={}", message.sleep_time),
+                    });
+
                     let parent_cx = global::get_text_map_propagator(|propagator| propagator.extract(&req_header));
                     let tracer = global::tracer("motion.rs");
                     let _span = tracer
@@ -195,7 +420,8 @@ async fn motion(workspace: zenoh::Workspace<'_>) {
 
                     // Sleep to simulate motion control
                     task::sleep(time::Duration::from_millis(message.sleep_time)).await;
-                };  
+                    sleep_histogram.record(message.sleep_time, &[]);
+                };
             }
 
             _ = stdin.read_exact(&mut input).fuse() => {
@@ -207,27 +433,98 @@ async fn motion(workspace: zenoh::Workspace<'_>) {
 }
 
 #[inline]
-fn init_tracer(svc_name: &str, version: &str, collector_endpoint: &str) -> Result<sdktrace::Tracer, TraceError> {
-    // W3C spec: https://www.w3.org/TR/trace-context/ - only for trace context info
-    global::set_text_map_propagator(TraceContextPropagator::new());
+fn init_tracer(svc_name: &str, version: &str, collector_endpoint: &str, exporter: &str) -> Result<sdktrace::Tracer, TraceError> {
+    // W3C spec: https://www.w3.org/TR/trace-context/ and https://www.w3.org/TR/baggage/ -
+    // propagate both the trace context (traceparent/tracestate) and user baggage.
+    // Datadog agents don't understand those headers, so swap in Datadog's own
+    // propagator (x-datadog-trace-id/x-datadog-parent-id) when that backend is selected.
+    if exporter == "datadog" {
+        global::set_text_map_propagator(DatadogPropagator::new());
+    } else {
+        global::set_text_map_propagator(TextMapCompositePropagator::new(vec![
+            Box::new(TraceContextPropagator::new()),
+            Box::new(BaggagePropagator::new()),
+        ]));
+    }
 
     // (Option) A set of standardized attributes, ref: https://github.com/open-telemetry/opentelemetry-specification/tree/main/specification/resource/semantic_conventions
-    let tags = [
+    let tags = vec![
         resource::SERVICE_VERSION.string(version.to_owned()),
         resource::PROCESS_EXECUTABLE_PATH.string(std::env::current_exe().unwrap().display().to_string()),
         resource::PROCESS_PID.string(std::process::id().to_string()),
     ];
 
-    // Initialize the tracker with jaeger as backend
-    opentelemetry_jaeger::new_pipeline()
-        .with_service_name(svc_name)
-        .with_collector_endpoint(format!("http://{}/api/traces", collector_endpoint))
-        .with_tags(tags.iter().map(ToOwned::to_owned))
-        .install_batch(opentelemetry::runtime::AsyncStd)
+    match exporter {
+        // OTLP/gRPC: the native export path for modern collectors (Jaeger 1.35+, the OTel Collector, etc.)
+        "otlp-grpc" => {
+            let mut resource_tags = tags.clone();
+            resource_tags.push(resource::SERVICE_NAME.string(svc_name.to_owned()));
+
+            opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .tonic()
+                        .with_endpoint(collector_endpoint.to_owned()),
+                )
+                .with_trace_config(sdktrace::config().with_resource(Resource::new(resource_tags)))
+                .install_batch(opentelemetry::runtime::AsyncStd)
+        }
+        // OTLP/HTTP+protobuf: same data model, for collectors that only expose the HTTP receiver
+        "otlp-http" => {
+            let mut resource_tags = tags.clone();
+            resource_tags.push(resource::SERVICE_NAME.string(svc_name.to_owned()));
+
+            opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(
+                    opentelemetry_otlp::new_exporter()
+                        .http()
+                        .with_endpoint(collector_endpoint.to_owned()),
+                )
+                .with_trace_config(sdktrace::config().with_resource(Resource::new(resource_tags)))
+                .install_batch(opentelemetry::runtime::AsyncStd)
+        }
+        // Datadog Agent's native trace ingest endpoint
+        "datadog" => {
+            let mut resource_tags = tags.clone();
+            resource_tags.push(resource::SERVICE_NAME.string(svc_name.to_owned()));
+
+            opentelemetry_datadog::new_pipeline()
+                .with_service_name(svc_name)
+                .with_agent_endpoint(collector_endpoint)
+                .with_trace_config(sdktrace::config().with_resource(Resource::new(resource_tags)))
+                .install_batch(opentelemetry::runtime::AsyncStd)
+        }
+        // Jaeger's native exporter, kept for backends still on the Thrift/HTTP collector API
+        _ => opentelemetry_jaeger::new_pipeline()
+            .with_service_name(svc_name)
+            .with_collector_endpoint(format!("http://{}/api/traces", collector_endpoint))
+            .with_tags(tags.iter().map(ToOwned::to_owned))
+            .install_batch(opentelemetry::runtime::AsyncStd),
+    }
 }
 
 
-fn parse_args() -> (Properties, String, String) {
+#[inline]
+fn init_meter(collector_endpoint: &str) -> Result<(), MetricsError> {
+    // Metrics always flow over OTLP/gRPC, independent of which tracing backend
+    // was selected for --exporter, but the endpoint is still configurable via
+    // --metrics-collector since not every --exporter's --collector value is a
+    // valid OTLP/gRPC target.
+    let provider = opentelemetry_otlp::new_pipeline()
+        .metrics(opentelemetry::runtime::AsyncStd)
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(collector_endpoint.to_owned()),
+        )
+        .build()?;
+    global::set_meter_provider(provider);
+    Ok(())
+}
+
+fn parse_args() -> (Properties, String, String, String, Vec<CollectorConfig>, String, String) {
     let args = App::new("opentelemery-zenoh example")
         .arg(
             Arg::from_usage("-m, --mode=[MODE] 'The zenoh session mode (peer by default).")
@@ -242,14 +539,28 @@ fn parse_args() -> (Properties, String, String) {
         .arg(Arg::from_usage(
             "-c, --config=[FILE]      'A configuration file.'",
         ))
+        .arg(Arg::from_usage(
+            "-o, --collector=[LOCATOR]      'The address of the span exporter's collector (default depends on --exporter).'",
+        ))
+        .arg(Arg::from_usage(
+            "-u, --metrics-collector=[LOCATOR]      'The address of the OTLP/gRPC metrics collector.'",
+        ).default_value("http://localhost:4317"))
+        .arg(Arg::from_usage(
+            "-t, --telemetry-collector=[LOCATOR]...      'A raw telemetry sink for the background TelemetryWorker to stream spans/events to, optionally suffixed with @VERBOSITY (0-9, higher is more verbose; default 9). May be repeated to fan out to several sinks. Distinct from --collector: this is dialed directly over TCP, not via OTLP.'",
+        ))
+        .arg(
+            Arg::from_usage("-a, --action=[MODE] 'The action of node (sensor by default).")
+                .possible_values(&["sensor", "computing", "motion"]),
+        )
         .arg(
             Arg::from_usage(
-                "-o, --collector=[LOCATOR]      'The address of the collector to collect data'")
-                .default_value("localhost:14268"),
+                "-x, --exporter=[BACKEND] 'The telemetry backend to export traces to (jaeger by default).'")
+                .possible_values(&["jaeger", "otlp-grpc", "otlp-http", "datadog"]),
         )
         .arg(
-            Arg::from_usage("-a, --action=[MODE] 'The action of node (sensor by default).")
-                .possible_values(&["sensor", "computing", "motion"]),
+            Arg::from_usage(
+                "-k, --context-carrier=[MODE] 'Where the propagated trace context travels: in the JSON payload, or in zenoh sideband metadata (payload by default).'")
+                .possible_values(&["payload", "sideband"]),
         )
         .arg(Arg::from_usage(
             "--no-multicast-scouting 'Disable the multicast-based scouting mechanism.'",
@@ -270,8 +581,55 @@ fn parse_args() -> (Properties, String, String) {
         config.insert("multicast_scouting".to_string(), "false".to_string());
     }
 
-    let collector = args.value_of("collector").unwrap().to_string();
     let action = args.value_of("action").unwrap().to_string();
-    
-    (config, collector, action)
+    let exporter = args.value_of("exporter").unwrap_or("jaeger").to_string();
+
+    let collector = args
+        .value_of("collector")
+        .map(ToOwned::to_owned)
+        .unwrap_or_else(|| default_collector_endpoint(&exporter));
+
+    let metrics_collector = args.value_of("metrics-collector").unwrap().to_string();
+
+    let telemetry_collectors: Vec<CollectorConfig> = args
+        .values_of("telemetry-collector")
+        .map(|values| values.map(parse_collector_entry).collect())
+        .unwrap_or_default();
+
+    let context_carrier = args.value_of("context-carrier").unwrap_or("payload").to_string();
+
+    (
+        config,
+        collector,
+        action,
+        exporter,
+        telemetry_collectors,
+        context_carrier,
+        metrics_collector,
+    )
+}
+
+const DEFAULT_COLLECTOR_VERBOSITY: u8 = 9;
+
+fn default_collector_endpoint(exporter: &str) -> String {
+    match exporter {
+        "otlp-grpc" => "http://localhost:4317".to_string(),
+        "otlp-http" => "http://localhost:4318/v1/traces".to_string(),
+        "datadog" => "http://localhost:8126".to_string(),
+        _ => "localhost:14268".to_string(),
+    }
+}
+
+// Parses a `--telemetry-collector` value of the form `ENDPOINT` or `ENDPOINT@VERBOSITY`.
+fn parse_collector_entry(entry: &str) -> CollectorConfig {
+    match entry.rsplit_once('@') {
+        Some((endpoint, verbosity)) => CollectorConfig {
+            endpoint: endpoint.to_string(),
+            verbosity: verbosity.parse().unwrap_or(DEFAULT_COLLECTOR_VERBOSITY),
+        },
+        None => CollectorConfig {
+            endpoint: entry.to_string(),
+            verbosity: DEFAULT_COLLECTOR_VERBOSITY,
+        },
+    }
 }
@@ -0,0 +1,163 @@
+//! A resilient, fan-out telemetry transport modeled on Substrate's
+//! `sc-telemetry`.
+//!
+//! Rather than exporting spans/events inline on the hot path, callers hand
+//! records to a [`TelemetryWorker`], which owns one background `async_std`
+//! task per configured collector. Each collector has its own channel,
+//! bounded backlog, and reconnect backoff, so a single unreachable sink can
+//! never stall delivery to the others.
+
+use async_std::channel::{bounded, Receiver, Sender, TrySendError};
+use async_std::net::TcpStream;
+use async_std::task;
+use std::time::Duration;
+
+const CHANNEL_CAPACITY: usize = 1024;
+const PER_COLLECTOR_BACKLOG: usize = 256;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// One configured telemetry sink: where to send records, and how verbose a
+/// record has to be for this sink to want it (a record is forwarded when
+/// `collector.verbosity >= record.verbosity`).
+#[derive(Clone, Debug)]
+pub struct CollectorConfig {
+    pub endpoint: String,
+    pub verbosity: u8,
+}
+
+/// A single span/event handed to the worker for delivery.
+#[derive(Clone, Debug)]
+pub struct TelemetryRecord {
+    pub verbosity: u8,
+    pub payload: String,
+}
+
+/// A configured sink's channel, paired with the verbosity it was configured
+/// with so `TelemetryWorker::send` can filter before ever touching the
+/// channel.
+struct SinkHandle {
+    verbosity: u8,
+    sender: Sender<TelemetryRecord>,
+}
+
+/// Handle to the background telemetry worker. Cheap to clone: it just holds
+/// one sender per configured sink.
+#[derive(Clone)]
+pub struct TelemetryWorker {
+    sinks: std::sync::Arc<Vec<SinkHandle>>,
+}
+
+impl TelemetryWorker {
+    /// Spawn one independent background task per collector and return a
+    /// handle spans/events can send records through.
+    pub fn spawn(collectors: Vec<CollectorConfig>) -> Self {
+        let sinks = collectors
+            .into_iter()
+            .map(|config| {
+                let (sender, receiver) = bounded(CHANNEL_CAPACITY);
+                let verbosity = config.verbosity;
+                task::spawn(CollectorSink::new(config).run(receiver));
+                SinkHandle { verbosity, sender }
+            })
+            .collect();
+        TelemetryWorker { sinks: std::sync::Arc::new(sinks) }
+    }
+
+    /// Hand a record to every sink whose verbosity wants it. Never blocks:
+    /// if a sink's task is falling behind (e.g. it's blocked on its own
+    /// reconnect backoff), the record is dropped for that sink only, rather
+    /// than stalling the sensor/computing/motion hot path or any other sink.
+    pub fn send(&self, record: TelemetryRecord) {
+        for sink in self.sinks.iter() {
+            if sink.verbosity < record.verbosity {
+                continue;
+            }
+            if let Err(TrySendError::Full(_)) = sink.sender.try_send(record.clone()) {
+                eprintln!("telemetry worker: sink channel full, dropping record");
+            }
+        }
+    }
+}
+
+/// Per-collector connection state: a bounded backlog plus reconnect backoff.
+/// Owns its own task via `run`, so its backoff sleep never blocks any other
+/// sink.
+struct CollectorSink {
+    config: CollectorConfig,
+    backlog: Vec<TelemetryRecord>,
+    connection: Option<TcpStream>,
+    backoff: Duration,
+}
+
+impl CollectorSink {
+    fn new(config: CollectorConfig) -> Self {
+        CollectorSink {
+            config,
+            backlog: Vec::new(),
+            connection: None,
+            backoff: INITIAL_BACKOFF,
+        }
+    }
+
+    async fn run(mut self, receiver: Receiver<TelemetryRecord>) {
+        while let Ok(record) = receiver.recv().await {
+            self.forward(record).await;
+        }
+    }
+
+    async fn forward(&mut self, record: TelemetryRecord) {
+        if self.backlog.len() >= PER_COLLECTOR_BACKLOG {
+            self.backlog.remove(0);
+        }
+        self.backlog.push(record);
+        self.drain().await;
+    }
+
+    async fn drain(&mut self) {
+        if self.connection.is_none() {
+            self.reconnect().await;
+        }
+        loop {
+            let record = match self.backlog.first().cloned() {
+                Some(record) => record,
+                None => break,
+            };
+            let stream = match self.connection.as_mut() {
+                Some(stream) => stream,
+                None => break,
+            };
+            match Self::write_record(stream, &record).await {
+                Ok(()) => {
+                    self.backlog.remove(0);
+                    self.backoff = INITIAL_BACKOFF;
+                }
+                Err(_) => {
+                    self.connection = None;
+                    self.reconnect().await;
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn reconnect(&mut self) {
+        match TcpStream::connect(&self.config.endpoint).await {
+            Ok(stream) => {
+                self.connection = Some(stream);
+                self.backoff = INITIAL_BACKOFF;
+            }
+            Err(_) => {
+                task::sleep(self.backoff).await;
+                self.backoff = (self.backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+
+    async fn write_record(stream: &mut TcpStream, record: &TelemetryRecord) -> std::io::Result<()> {
+        use async_std::io::WriteExt;
+        let mut line = record.payload.clone();
+        line.push('\n');
+        stream.write_all(line.as_bytes()).await
+    }
+}